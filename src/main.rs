@@ -3,51 +3,89 @@
 //! This type of implementations is useful for representing the kind of
 //! graphs that occur in compilers, such as a Graph IR, CFG of basic
 //! blocks and so on.
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Write};
 
 /// The root node is always at 0th index.
 const ROOT: NodeRef = NodeRef(0);
 
 /// Strongly typed reference to a node in the graph.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NodeRef(pub usize);
 
-/// Node in the graph, could hold anything, for example it could
-/// hold code and a list of edges to children.
+/// Strongly typed reference to an edge in the graph's central edge list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct EdgeRef(usize);
+
+/// Node in the graph, parameterized over a node payload `N`, for example
+/// an instruction block in a Graph IR. Rather than owning its edges, a
+/// node only holds the heads of two intrusive singly-linked lists into
+/// the graph's central edge list: one walking its outgoing edges, one
+/// walking its incoming edges.
 #[derive(Debug)]
-pub struct Node {
+pub struct Node<N> {
     // Identifier of the node.
     id: NodeRef,
-    // Node label.
-    pub label: String,
-    // Edges for the node, you can have more complex edges using an enum
-    // such as `Vec<(NodeRef, Edge)>` to represent edges that are unconditional
-    // for example if the basic block branches to another edge regardless
-    // of its internal state, or a conditional edge that has some input.
-    edges: Vec<NodeRef>,
-}
-
-/// A Graph represented as a `Vec<Node>`, the struct can be more complex
-/// and hold more metadata but for our purposes it will hold just the
-/// nodes.
-pub struct Graph {
+    // User supplied node payload.
+    data: N,
+    // Head of the outgoing edge list, or `None` if this node has no
+    // successors.
+    first_outgoing: Option<EdgeRef>,
+    // Head of the incoming edge list, or `None` if this node has no
+    // predecessors.
+    first_incoming: Option<EdgeRef>,
+}
+
+/// Edge in the graph's central edge list, carrying a payload `E` (for
+/// example a conditional/unconditional branch) plus the intrusive links
+/// that thread it onto its source's outgoing list and its target's
+/// incoming list.
+#[derive(Debug)]
+struct Edge<E> {
+    source: NodeRef,
+    target: NodeRef,
+    data: E,
+    // Next edge in `source`'s outgoing list.
+    next_outgoing: Option<EdgeRef>,
+    // Next edge in `target`'s incoming list.
+    next_incoming: Option<EdgeRef>,
+}
+
+/// A Graph represented as a `Vec<Node>` plus a central `Vec<Edge>`, the
+/// struct can be more complex and hold more metadata but for our
+/// purposes it will hold just the nodes and edges. Generic over a node
+/// payload `N` and an edge payload `E`, so the same flat structure can
+/// back a Graph IR (`N` = instruction block, `E` = conditional/
+/// unconditional branch) without wrapper types.
+pub struct Graph<N, E> {
     /// Nodes in the graph.
-    nodes: Vec<Node>,
+    nodes: Vec<Node<N>>,
+    /// All edges in the graph, regardless of which node they touch.
+    edges: Vec<Edge<E>>,
 }
 
-impl Graph {
+impl<N, E> Graph<N, E> {
     /// Create a new empty graph.
     pub fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
     }
 
     /// Add a new unlinked node to the graph.
-    pub fn add_node(&mut self) -> NodeRef {
+    pub fn add_node(&mut self, data: N) -> NodeRef {
         let node_id = self.nodes.len();
         let node = Node {
             id: NodeRef(node_id),
-            label: format!(".L{node_id}"),
-            edges: Vec::new(),
+            data,
+            first_outgoing: None,
+            first_incoming: None,
         };
 
         self.nodes.push(node);
@@ -56,75 +94,658 @@ impl Graph {
     }
 
     /// Add a new linked node to the graph.
-    pub fn add_node_linked(&mut self, edges: Vec<NodeRef>) -> NodeRef {
+    pub fn add_node_linked(&mut self, data: N, edges: Vec<(NodeRef, E)>) -> NodeRef {
         let node_id = self.nodes.len();
         let node = Node {
             id: NodeRef(node_id),
-            label: format!(".L{node_id}"),
-            edges: edges.clone(),
+            data,
+            first_outgoing: None,
+            first_incoming: None,
         };
         self.nodes.push(node);
 
-        for &edge in &edges {
-            self.link(NodeRef(node_id), edge)
+        for (edge, data) in edges {
+            self.link(NodeRef(node_id), edge, data)
         }
 
         NodeRef(node_id)
     }
 
     /// Return an immutable reference to a node.
-    pub fn node_as_ref(&self, id: NodeRef) -> Option<&Node> {
+    pub fn node_as_ref(&self, id: NodeRef) -> Option<&Node<N>> {
         self.nodes.get(id.0)
     }
 
     /// Return a mutable reference to a node.
-    pub fn node_as_mut_ref(&mut self, id: NodeRef) -> Option<&mut Node> {
+    pub fn node_as_mut_ref(&mut self, id: NodeRef) -> Option<&mut Node<N>> {
         self.nodes.get_mut(id.0)
     }
 
-    /// Link two nodes in the graph.
-    pub fn link(&mut self, from: NodeRef, to: NodeRef) {
-        let src_node = self.nodes.get_mut(from.0).unwrap();
+    /// Return an immutable reference to a node's payload.
+    pub fn node_data(&self, id: NodeRef) -> &N {
+        &self.nodes[id.0].data
+    }
 
-        src_node.edges.push(to);
+    /// Return a mutable reference to a node's payload.
+    pub fn node_data_mut(&mut self, id: NodeRef) -> &mut N {
+        &mut self.nodes[id.0].data
+    }
+
+    /// Return a reference to the payload carried by the edge linking
+    /// `from` to `to`, if such an edge exists.
+    pub fn edge_data(&self, from: NodeRef, to: NodeRef) -> Option<&E> {
+        let mut cursor = self.nodes[from.0].first_outgoing;
+        while let Some(edge_ref) = cursor {
+            let edge = &self.edges[edge_ref.0];
+            if edge.target == to {
+                return Some(&edge.data);
+            }
+            cursor = edge.next_outgoing;
+        }
+        None
     }
 
-    /// Walk the graph in BFS order.
+    /// Link two nodes in the graph, attaching an edge payload. The new
+    /// edge is spliced onto both the source's outgoing list and the
+    /// target's incoming list in O(1).
+    pub fn link(&mut self, from: NodeRef, to: NodeRef, data: E) {
+        let edge_ref = EdgeRef(self.edges.len());
+        self.edges.push(Edge {
+            source: from,
+            target: to,
+            data,
+            next_outgoing: self.nodes[from.0].first_outgoing,
+            next_incoming: self.nodes[to.0].first_incoming,
+        });
+
+        self.nodes[from.0].first_outgoing = Some(edge_ref);
+        self.nodes[to.0].first_incoming = Some(edge_ref);
+    }
+
+    /// Iterate over the successors of `node`, i.e. the targets of its
+    /// outgoing edges.
+    pub fn successors(&self, node: NodeRef) -> Successors<'_, N, E> {
+        Successors(self.outgoing(node))
+    }
+
+    /// Iterate over the predecessors of `node`, i.e. the sources of its
+    /// incoming edges. Essential for dataflow and SSA construction, where
+    /// a basic block needs to ask "who branches to me?".
+    pub fn predecessors(&self, node: NodeRef) -> Predecessors<'_, N, E> {
+        Predecessors {
+            graph: self,
+            next: self.nodes[node.0].first_incoming,
+        }
+    }
+
+    /// Walk the graph in BFS order from [`ROOT`], reporting each node
+    /// paired with each of its successors in turn (or paired with `None`
+    /// if it has none). A thin wrapper over [`Bfs`] kept for backward
+    /// compatibility; prefer [`Graph::bfs`]/[`Graph::dfs`]/
+    /// [`Graph::dfs_postorder`] directly for new code.
     pub fn walk<F>(&self, mut visitor: F)
     where
-        F: FnMut(&Node, Option<&Node>),
+        F: FnMut(&Node<N>, Option<&Node<N>>),
     {
-        // BFS visit state.
-        let mut visited = vec![false; self.nodes.len()];
+        for node in self.bfs(ROOT) {
+            let node_ref = self.node_as_ref(node).unwrap();
 
-        // BFS queue.
-        let mut queue = VecDeque::new();
-        // Since we start at the root push it first.
-        queue.push_back(ROOT);
+            let mut successors = self.successors(node).peekable();
+            if successors.peek().is_some() {
+                for successor in successors {
+                    visitor(node_ref, self.node_as_ref(successor));
+                }
+            } else {
+                visitor(node_ref, None);
+            }
+        }
+    }
 
-        while let Some(node) = queue.pop_front() {
-            // Check if we visited this node and we should skip it.
-            if visited[node.0] {
-                continue;
+    /// Iterate over the nodes reachable from `start` in breadth-first
+    /// order, each exactly once.
+    pub fn bfs(&self, start: NodeRef) -> Bfs<'_, N, E> {
+        Bfs::new(self, start)
+    }
+
+    /// Iterate over the nodes reachable from `start` in depth-first
+    /// pre-order, each exactly once.
+    pub fn dfs(&self, start: NodeRef) -> Dfs<'_, N, E> {
+        Dfs::new(self, start)
+    }
+
+    /// Iterate over the nodes reachable from `start` in depth-first
+    /// post-order, each exactly once. Reversing this order yields the
+    /// reverse-postorder numbering the dominator pass needs.
+    pub fn dfs_postorder(&self, start: NodeRef) -> DfsPostOrder<'_, N, E> {
+        DfsPostOrder::new(self, start)
+    }
+
+    /// Compute the dominator tree of the subgraph reachable from `root`,
+    /// using the iterative Cooper-Harvey-Kennedy algorithm.
+    pub fn dominators(&self, root: NodeRef) -> Dominators {
+        // Reverse postorder numbering of nodes reachable from `root`.
+        let rpo = self.reverse_postorder(root);
+        let mut rpo_number: Vec<Option<usize>> = vec![None; self.nodes.len()];
+        for (i, &node) in rpo.iter().enumerate() {
+            rpo_number[node.0] = Some(i);
+        }
+
+        let mut idom: Vec<Option<NodeRef>> = vec![None; self.nodes.len()];
+        idom[root.0] = Some(root);
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            // `rpo[0]` is always `root`, whose idom is fixed above.
+            for &node in &rpo[1..] {
+                let mut new_idom = None;
+                for pred in self.predecessors(node) {
+                    if idom[pred.0].is_none() {
+                        // Predecessor not yet processed this pass.
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => pred,
+                        Some(cur) => Self::intersect(&idom, &rpo_number, cur, pred),
+                    });
+                }
+
+                if idom[node.0] != new_idom {
+                    idom[node.0] = new_idom;
+                    changed = true;
+                }
             }
+        }
 
-            // Mark it as visited.
-            visited[node.0] = true;
+        Dominators { root, idom }
+    }
 
-            let node = self.node_as_ref(node).unwrap();
+    /// Walk the two finger pointers `a` and `b` up the partially built
+    /// dominator tree, each time raising whichever finger sits at the
+    /// larger reverse-postorder number to its current immediate
+    /// dominator, until they meet at the common dominator. The root has
+    /// the smallest rpo number and `idom[x]`'s rpo number is always
+    /// smaller than `x`'s, so raising the larger one walks it toward the
+    /// root.
+    fn intersect(
+        idom: &[Option<NodeRef>],
+        rpo_number: &[Option<usize>],
+        mut a: NodeRef,
+        mut b: NodeRef,
+    ) -> NodeRef {
+        while a != b {
+            if rpo_number[a.0] > rpo_number[b.0] {
+                a = idom[a.0].unwrap();
+            } else {
+                b = idom[b.0].unwrap();
+            }
+        }
+        a
+    }
+
+    /// Reverse postorder numbering of the nodes reachable from `root`.
+    fn reverse_postorder(&self, root: NodeRef) -> Vec<NodeRef> {
+        let mut postorder: Vec<NodeRef> = self.dfs_postorder(root).collect();
+        postorder.reverse();
+        postorder
+    }
+
+    /// Count the weakly connected components of the graph, treating
+    /// edges as undirected. Unlike [`Graph::walk`], which only visits
+    /// what's reachable from [`ROOT`], this sees every disconnected
+    /// fragment.
+    pub fn connected_components(&self) -> usize {
+        let mut uf = self.union_find();
+        let mut roots = HashSet::new();
+        for i in 0..self.nodes.len() {
+            roots.insert(uf.find(i));
+        }
+        roots.len()
+    }
 
-            if node.edges.len() > 0 {
-                // Queue up edges to visit next.
-                for &edge in &node.edges {
-                    // Call visitor function.
-                    visitor(node, self.node_as_ref(edge));
-                    queue.push_back(edge);
+    /// Return an id identifying the weakly connected component `node`
+    /// belongs to. Two nodes belong to the same component iff this
+    /// returns the same id for both.
+    pub fn component_of(&self, node: NodeRef) -> usize {
+        self.union_find().find(node.0)
+    }
+
+    /// Build a union-find over the nodes, unioning the endpoints of
+    /// every edge (treated as undirected).
+    fn union_find(&self) -> UnionFind {
+        let mut uf = UnionFind::new(self.nodes.len());
+        for edge in &self.edges {
+            uf.union(edge.source.0, edge.target.0);
+        }
+        uf
+    }
+
+    /// Iterate over `node`'s outgoing edges as `(target, data)` pairs,
+    /// for callers that need the edge payload alongside the successor
+    /// (e.g. [`Graph::dijkstra`]) without re-scanning it via
+    /// [`Graph::edge_data`].
+    fn outgoing(&self, node: NodeRef) -> Outgoing<'_, N, E> {
+        Outgoing {
+            graph: self,
+            next: self.nodes[node.0].first_outgoing,
+        }
+    }
+
+    /// Compute the minimum cost from `start` to every reachable node,
+    /// using `edge_cost` to price each edge. Standard Dijkstra with a
+    /// min-heap of `(cost, node)` pairs, skipping stale heap entries
+    /// whose recorded cost is worse than the settled distance.
+    pub fn dijkstra(
+        &self,
+        start: NodeRef,
+        edge_cost: impl Fn(&E) -> u64,
+    ) -> HashMap<NodeRef, u64> {
+        let mut dist = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        dist.insert(start, 0);
+        heap.push(MinScored(0, start));
+
+        while let Some(MinScored(cost, node)) = heap.pop() {
+            if cost > *dist.get(&node).unwrap_or(&u64::MAX) {
+                // Stale entry superseded by a cheaper path already found.
+                continue;
+            }
+
+            for (successor, data) in self.outgoing(node) {
+                let next_cost = cost + edge_cost(data);
+                if next_cost < *dist.get(&successor).unwrap_or(&u64::MAX) {
+                    dist.insert(successor, next_cost);
+                    heap.push(MinScored(next_cost, successor));
                 }
+            }
+        }
+
+        dist
+    }
+}
+
+/// Wraps a `(cost, value)` pair so a [`BinaryHeap`], which is a max-heap
+/// by default, pops the lowest cost first.
+struct MinScored<T>(u64, T);
+
+impl<T> PartialEq for MinScored<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for MinScored<T> {}
+
+impl<T> PartialOrd for MinScored<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for MinScored<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+/// Disjoint-set structure with path compression and union-by-rank, used
+/// by [`Graph::connected_components`] to find weakly connected
+/// components in close to O(1) per operation.
+struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            rank: vec![0; len],
+        }
+    }
+
+    /// Find the representative of `x`'s set, compressing the path to it.
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    /// Union the sets containing `a` and `b`, attaching the
+    /// shallower-ranked tree under the deeper one.
+    fn union(&mut self, a: usize, b: usize) {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+        if root_a == root_b {
+            return;
+        }
+
+        match self.rank[root_a].cmp(&self.rank[root_b]) {
+            std::cmp::Ordering::Less => self.parent[root_a] = root_b,
+            std::cmp::Ordering::Greater => self.parent[root_b] = root_a,
+            std::cmp::Ordering::Equal => {
+                self.parent[root_b] = root_a;
+                self.rank[root_a] += 1;
+            }
+        }
+    }
+}
+
+impl<N: fmt::Debug, E> Graph<N, E> {
+    /// Render this graph as Graphviz DOT, using the default attribute
+    /// hooks (`{:?}` as the node label, no edge attributes). Use
+    /// [`Graph::dot`] to customize node/edge attributes before writing.
+    pub fn to_dot(&self, out: &mut impl Write) -> io::Result<()> {
+        self.dot().write(out)
+    }
+
+    /// Build a [`Dot`] exporter for this graph, which can be customized
+    /// with [`Dot::with_node_attr`] and [`Dot::with_edge_attr`] before
+    /// writing, for example to color basic blocks or annotate branch
+    /// edges.
+    pub fn dot(&self) -> Dot<'_, N, E> {
+        Dot::new(self)
+    }
+}
+
+/// Per-node DOT attribute hook, see [`Dot::with_node_attr`].
+type NodeAttr<'graph, N> = Box<dyn Fn(NodeRef, &N) -> String + 'graph>;
+/// Per-edge DOT attribute hook, see [`Dot::with_edge_attr`].
+type EdgeAttr<'graph, E> = Box<dyn Fn(NodeRef, NodeRef, &E) -> String + 'graph>;
+
+/// Graphviz DOT exporter for a [`Graph`], built via [`Graph::dot`].
+/// Node and edge attribute strings default to `{:?}`-labelling the node
+/// payload and leaving edges bare, but can be overridden with closures
+/// via [`Dot::with_node_attr`] and [`Dot::with_edge_attr`], mirroring
+/// the trait-based labelling the `graphviz` crate exposes.
+pub struct Dot<'graph, N, E> {
+    graph: &'graph Graph<N, E>,
+    node_attr: NodeAttr<'graph, N>,
+    edge_attr: EdgeAttr<'graph, E>,
+}
+
+impl<'graph, N: fmt::Debug, E> Dot<'graph, N, E> {
+    /// Create a new exporter with default attribute hooks.
+    pub fn new(graph: &'graph Graph<N, E>) -> Self {
+        Self {
+            graph,
+            // `{data:?}` already quotes and escapes `&str`/`String`
+            // payloads, so `label=` must not wrap it in a second pair
+            // of quotes.
+            node_attr: Box::new(|_, data| format!("label={data:?}")),
+            edge_attr: Box::new(|_, _, _| String::new()),
+        }
+    }
+}
+
+impl<'graph, N, E> Dot<'graph, N, E> {
+    /// Override how a node's DOT attributes (everything inside the
+    /// `[...]` after `N<id>`) are rendered.
+    pub fn with_node_attr(mut self, f: impl Fn(NodeRef, &N) -> String + 'graph) -> Self {
+        self.node_attr = Box::new(f);
+        self
+    }
+
+    /// Override how an edge's DOT attributes are rendered. Return an
+    /// empty string to omit the `[...]` block entirely.
+    pub fn with_edge_attr(mut self, f: impl Fn(NodeRef, NodeRef, &E) -> String + 'graph) -> Self {
+        self.edge_attr = Box::new(f);
+        self
+    }
+
+    /// Write this graph to `out` as a single `digraph { ... }` block.
+    pub fn write(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "{self}")
+    }
+}
+
+impl<'graph, N, E> fmt::Display for Dot<'graph, N, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph {{")?;
+
+        for (i, node) in self.graph.nodes.iter().enumerate() {
+            let id = NodeRef(i);
+            writeln!(f, "    N{i} [{}];", (self.node_attr)(id, &node.data))?;
+        }
+
+        for edge in &self.graph.edges {
+            let attrs = (self.edge_attr)(edge.source, edge.target, &edge.data);
+            if attrs.is_empty() {
+                writeln!(f, "    N{} -> N{};", edge.source.0, edge.target.0)?;
             } else {
-                // Node has no edges.
-                visitor(node, None);
+                writeln!(f, "    N{} -> N{} [{attrs}];", edge.source.0, edge.target.0)?;
+            }
+        }
+
+        writeln!(f, "}}")
+    }
+}
+
+/// Dominator tree computed by [`Graph::dominators`].
+pub struct Dominators {
+    root: NodeRef,
+    idom: Vec<Option<NodeRef>>,
+}
+
+impl Dominators {
+    /// Return the immediate dominator of `node`, or `None` if `node` is
+    /// the root or was never reached by the traversal that built this
+    /// tree.
+    pub fn immediate_dominator(&self, node: NodeRef) -> Option<NodeRef> {
+        if node == self.root {
+            None
+        } else {
+            self.idom[node.0]
+        }
+    }
+
+    /// Iterate over the dominators of `node`, from `node` itself up to
+    /// the root, by walking the immediate-dominator chain.
+    pub fn dominators(&self, node: NodeRef) -> DominatorChain<'_> {
+        DominatorChain {
+            tree: self,
+            current: Some(node),
+        }
+    }
+}
+
+/// Lazy iterator over a node's dominators, walking the idom chain to the
+/// root.
+pub struct DominatorChain<'tree> {
+    tree: &'tree Dominators,
+    current: Option<NodeRef>,
+}
+
+impl<'tree> Iterator for DominatorChain<'tree> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.current?;
+        self.current = if node == self.tree.root {
+            None
+        } else {
+            self.tree.idom[node.0]
+        };
+        Some(node)
+    }
+}
+
+/// Lazy iterator over a node's outgoing `(target, data)` edges, walking
+/// the `next_outgoing` intrusive list. Built by [`Graph::outgoing`];
+/// [`Successors`] and [`Graph::dijkstra`] sit on top of it, while
+/// [`Graph::edge_data`] walks the list with its own loop.
+struct Outgoing<'graph, N, E> {
+    graph: &'graph Graph<N, E>,
+    next: Option<EdgeRef>,
+}
+
+impl<'graph, N, E> Iterator for Outgoing<'graph, N, E> {
+    type Item = (NodeRef, &'graph E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let edge_ref = self.next?;
+        let edge = &self.graph.edges[edge_ref.0];
+        self.next = edge.next_outgoing;
+        Some((edge.target, &edge.data))
+    }
+}
+
+/// Lazy iterator over a node's successors, walking the `next_outgoing`
+/// intrusive list.
+pub struct Successors<'graph, N, E>(Outgoing<'graph, N, E>);
+
+impl<'graph, N, E> Iterator for Successors<'graph, N, E> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(target, _)| target)
+    }
+}
+
+/// Lazy iterator over a node's predecessors, walking the `next_incoming`
+/// intrusive list.
+pub struct Predecessors<'graph, N, E> {
+    graph: &'graph Graph<N, E>,
+    next: Option<EdgeRef>,
+}
+
+impl<'graph, N, E> Iterator for Predecessors<'graph, N, E> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let edge_ref = self.next?;
+        let edge = &self.graph.edges[edge_ref.0];
+        self.next = edge.next_incoming;
+        Some(edge.source)
+    }
+}
+
+/// Lazy breadth-first traversal from a start node, visiting each
+/// reachable node exactly once.
+pub struct Bfs<'graph, N, E> {
+    graph: &'graph Graph<N, E>,
+    queue: VecDeque<NodeRef>,
+    visited: Vec<bool>,
+}
+
+impl<'graph, N, E> Bfs<'graph, N, E> {
+    pub fn new(graph: &'graph Graph<N, E>, start: NodeRef) -> Self {
+        let mut visited = vec![false; graph.nodes.len()];
+        visited[start.0] = true;
+
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+
+        Self {
+            graph,
+            queue,
+            visited,
+        }
+    }
+}
+
+impl<'graph, N, E> Iterator for Bfs<'graph, N, E> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+
+        for successor in self.graph.successors(node) {
+            if !self.visited[successor.0] {
+                self.visited[successor.0] = true;
+                self.queue.push_back(successor);
+            }
+        }
+
+        Some(node)
+    }
+}
+
+/// Lazy depth-first pre-order traversal from a start node, visiting each
+/// reachable node exactly once.
+pub struct Dfs<'graph, N, E> {
+    graph: &'graph Graph<N, E>,
+    stack: Vec<NodeRef>,
+    visited: Vec<bool>,
+}
+
+impl<'graph, N, E> Dfs<'graph, N, E> {
+    pub fn new(graph: &'graph Graph<N, E>, start: NodeRef) -> Self {
+        let mut visited = vec![false; graph.nodes.len()];
+        visited[start.0] = true;
+
+        Self {
+            graph,
+            stack: vec![start],
+            visited,
+        }
+    }
+}
+
+impl<'graph, N, E> Iterator for Dfs<'graph, N, E> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+
+        for successor in self.graph.successors(node) {
+            if !self.visited[successor.0] {
+                self.visited[successor.0] = true;
+                self.stack.push(successor);
+            }
+        }
+
+        Some(node)
+    }
+}
+
+/// Lazy depth-first post-order traversal from a start node, visiting
+/// each reachable node exactly once. Reversing the sequence this yields
+/// produces a reverse-postorder numbering, as used by
+/// [`Graph::dominators`].
+pub struct DfsPostOrder<'graph, N, E> {
+    graph: &'graph Graph<N, E>,
+    stack: Vec<(NodeRef, Successors<'graph, N, E>)>,
+    visited: Vec<bool>,
+}
+
+impl<'graph, N, E> DfsPostOrder<'graph, N, E> {
+    pub fn new(graph: &'graph Graph<N, E>, start: NodeRef) -> Self {
+        let mut visited = vec![false; graph.nodes.len()];
+        visited[start.0] = true;
+
+        Self {
+            graph,
+            stack: vec![(start, graph.successors(start))],
+            visited,
+        }
+    }
+}
+
+impl<'graph, N, E> Iterator for DfsPostOrder<'graph, N, E> {
+    type Item = NodeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, successors)) = self.stack.last_mut() {
+            let node = *node;
+            match successors.next() {
+                Some(next) => {
+                    if !self.visited[next.0] {
+                        self.visited[next.0] = true;
+                        self.stack.push((next, self.graph.successors(next)));
+                    }
+                }
+                None => {
+                    self.stack.pop();
+                    return Some(node);
+                }
             }
         }
+
+        None
     }
 }
 
@@ -135,24 +756,38 @@ fn main() {
     // root -> A -> B -
     //         |________\_D
     //
-    let mut graph = Graph::new();
-    let root = graph.add_node();
-    let a = graph.add_node();
-    let b = graph.add_node();
-    let c = graph.add_node();
-    let d = graph.add_node();
-
-    graph.link(root, a);
-    graph.link(a, b);
-    graph.link(b, c);
-    graph.link(b, d);
-    graph.link(d, a);
+    let mut graph: Graph<&str, ()> = Graph::new();
+    let root = graph.add_node("root");
+    let a = graph.add_node("A");
+    let b = graph.add_node("B");
+    let c = graph.add_node("C");
+    let d = graph.add_node("D");
+
+    graph.link(root, a, ());
+    graph.link(a, b, ());
+    graph.link(b, c, ());
+    graph.link(b, d, ());
+    graph.link(d, a, ());
 
     graph.walk(|from, to| {
         let to_label = match to {
-            Some(to) => to.label.as_str(),
+            Some(to) => to.data,
             None => "None",
         };
-        println!("Node {:?} is connected to {:?}", from.label, to_label);
+        println!("Node {:?} is connected to {:?}", from.data, to_label);
     });
+
+    for pred in graph.predecessors(a) {
+        println!("Predecessor of A: {:?}", graph.node_data(pred));
+    }
+
+    print!("{}", graph.dot());
+
+    println!("connected components: {}", graph.connected_components());
+
+    let postorder: Vec<_> = graph.dfs_postorder(root).collect();
+    println!("DFS postorder from root: {postorder:?}");
+
+    let distances = graph.dijkstra(root, |_| 1);
+    println!("distances from root: {distances:?}");
 }